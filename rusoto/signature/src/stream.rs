@@ -1,19 +1,83 @@
 use std::fmt;
 use std::io;
+use std::io::SeekFrom;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use bytes::{BufMut, Bytes, BytesMut};
+use async_trait::async_trait;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use futures::{future, stream, Stream, StreamExt};
 use pin_project_lite::pin_project;
-use tokio::io::{AsyncRead, ReadBuf};
+use tokio::io::{
+    AsyncBufRead, AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, ReadBuf,
+};
+
+/// Default chunk size used by [`ByteStream::from_async_read`].
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// Shared by [`ReaderStream`] and [`ResettableReaderSource`]: a `capacity` of 0 would make every
+/// `ReadBuf` passed to `poll_read` zero-length, so the first poll always reports `filled == 0`
+/// and the stream silently ends without reading anything from the underlying reader. Catch that
+/// here, at construction, instead of letting it masquerade as an empty body.
+fn assert_nonzero_capacity(capacity: usize) -> usize {
+    assert!(
+        capacity > 0,
+        "ByteStream chunk capacity must be greater than zero"
+    );
+    capacity
+}
+
+/// A `ByteStream` body that can be rewound back to its start, so that a failed request (for
+/// example a retried S3 `PutObject`) can replay the same body instead of failing outright.
+///
+/// `ByteStream`s built from in-memory buffers ([`From<Bytes>`], [`From<Vec<u8>>`]) or from
+/// [`ByteStream::from_seekable_async_read`] are resettable; arbitrary one-shot streams built
+/// from [`ByteStream::new`] are not, since there is no general way to replay a `Stream` once it
+/// has been consumed.
+#[async_trait]
+pub trait ResettableByteStream: Stream<Item = Result<Bytes, io::Error>> + Send {
+    /// Rewind this stream back to offset 0 so it can be read again from the start.
+    async fn reset(&mut self) -> io::Result<()>;
+}
+
+enum Inner {
+    Stream(Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send + 'static>>),
+    Resettable(Pin<Box<dyn ResettableByteStream + Send + Unpin + 'static>>),
+}
+
+impl Inner {
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes, io::Error>>> {
+        match self {
+            Inner::Stream(stream) => stream.as_mut().poll_next(cx),
+            Inner::Resettable(stream) => stream.as_mut().poll_next(cx),
+        }
+    }
+
+    fn into_boxed_stream(self) -> Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>> {
+        match self {
+            Inner::Stream(stream) => stream,
+            Inner::Resettable(stream) => Box::pin(ResettableAsStream(stream)),
+        }
+    }
+}
+
+/// Adapts a `Pin<Box<dyn ResettableByteStream>>` into a plain boxed `Stream`, since a trait
+/// object cannot be upcast from a subtrait to its supertrait on its own.
+struct ResettableAsStream(Pin<Box<dyn ResettableByteStream + Send + Unpin>>);
+
+impl Stream for ResettableAsStream {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
 
 pin_project! {
     /// Stream of bytes.
     pub struct ByteStream {
         size_hint: Option<usize>,
-        #[pin]
-        inner: Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send + 'static>>,
+        inner: Inner,
     }
 }
 
@@ -25,7 +89,7 @@ impl ByteStream {
     {
         ByteStream {
             size_hint: None,
-            inner: Box::pin(stream),
+            inner: Inner::Stream(Box::pin(stream)),
         }
     }
 
@@ -37,7 +101,7 @@ impl ByteStream {
     {
         ByteStream {
             size_hint: Some(size_hint),
-            inner: Box::pin(stream),
+            inner: Inner::Stream(Box::pin(stream)),
         }
     }
 
@@ -45,14 +109,108 @@ impl ByteStream {
         self.size_hint
     }
 
-    /// Return an implementation of `AsyncRead` that uses async i/o to consume the stream.
-    pub fn into_async_read(self) -> impl AsyncRead + Send {
-        ImplAsyncRead::new(self.inner)
+    /// Returns `true` if this body supports [`ByteStream::reset`].
+    pub fn is_resettable(&self) -> bool {
+        matches!(self.inner, Inner::Resettable(_))
+    }
+
+    /// Rewind this stream back to the beginning so it can be re-sent, e.g. after a retryable
+    /// request failure. Returns `Ok(false)` if this particular `ByteStream` is not resettable
+    /// (see [`ResettableByteStream`]) instead of failing.
+    pub async fn reset(&mut self) -> io::Result<bool> {
+        match &mut self.inner {
+            Inner::Resettable(stream) => {
+                stream.as_mut().get_mut().reset().await?;
+                Ok(true)
+            }
+            Inner::Stream(_) => Ok(false),
+        }
+    }
+
+    /// Return an implementation of `AsyncRead` (and `AsyncBufRead`) that uses async i/o to
+    /// consume the stream.
+    pub fn into_async_read(self) -> ByteStreamReader {
+        ByteStreamReader::new(self.inner.into_boxed_stream())
     }
 
     /// Return an implementation of `Read` that uses blocking i/o to consume the stream.
     pub fn into_blocking_read(self) -> impl io::Read + Send {
-        ImplBlockingRead::new(self.inner)
+        ImplBlockingRead::new(self.inner.into_boxed_stream())
+    }
+
+    /// Drain this stream into `writer`, returning the total number of bytes written. Each
+    /// chunk is written with `write_all_buf` so the `Bytes` is handed to `writer` directly,
+    /// without first copying it through an intermediate `&[u8]` read buffer.
+    pub async fn copy_to<W>(self, writer: &mut W) -> io::Result<u64>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut stream = self;
+        let mut written = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let mut chunk = chunk?;
+            written += chunk.len() as u64;
+            writer.write_all_buf(&mut chunk).await?;
+        }
+        Ok(written)
+    }
+
+    /// Create a new `ByteStream` by reading from an `AsyncRead` implementation, chunking the
+    /// data into `Bytes` of up to `DEFAULT_CAPACITY` as it is pumped through. This lets a caller
+    /// stream a file (or any other async reader) straight into a request body without buffering
+    /// the whole thing in memory first.
+    pub fn from_async_read<R>(reader: R, size_hint: Option<usize>) -> ByteStream
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        ByteStream::from_async_read_with_capacity(reader, size_hint, DEFAULT_CAPACITY)
+    }
+
+    /// Like [`ByteStream::from_async_read`], but allows the chunk size to be configured instead
+    /// of using `DEFAULT_CAPACITY`.
+    pub fn from_async_read_with_capacity<R>(
+        reader: R,
+        size_hint: Option<usize>,
+        capacity: usize,
+    ) -> ByteStream
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        ByteStream {
+            size_hint,
+            inner: Inner::Stream(Box::pin(ReaderStream::new(
+                reader,
+                assert_nonzero_capacity(capacity),
+            ))),
+        }
+    }
+
+    /// Create a new, resettable `ByteStream` backed by a seekable reader (e.g. a `tokio::fs::File`),
+    /// so that a failed request carrying this body can be retried via [`ByteStream::reset`].
+    pub fn from_seekable_async_read<R>(reader: R, size_hint: Option<usize>) -> ByteStream
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + 'static,
+    {
+        ByteStream::from_seekable_async_read_with_capacity(reader, size_hint, DEFAULT_CAPACITY)
+    }
+
+    /// Like [`ByteStream::from_seekable_async_read`], but allows the chunk size to be configured
+    /// instead of using `DEFAULT_CAPACITY`.
+    pub fn from_seekable_async_read_with_capacity<R>(
+        reader: R,
+        size_hint: Option<usize>,
+        capacity: usize,
+    ) -> ByteStream
+    where
+        R: AsyncRead + AsyncSeek + Unpin + Send + 'static,
+    {
+        ByteStream {
+            size_hint,
+            inner: Inner::Resettable(Box::pin(ResettableReaderSource::new(
+                reader,
+                assert_nonzero_capacity(capacity),
+            ))),
+        }
     }
 }
 
@@ -60,17 +218,14 @@ impl From<Bytes> for ByteStream {
     fn from(buf: Bytes) -> ByteStream {
         ByteStream {
             size_hint: Some(buf.len()),
-            inner: Box::pin(stream::once(async move { Ok(buf) })),
+            inner: Inner::Resettable(Box::pin(ResettableBytesSource::new(buf))),
         }
     }
 }
 
 impl From<Vec<u8>> for ByteStream {
     fn from(buf: Vec<u8>) -> ByteStream {
-        ByteStream {
-            size_hint: Some(buf.len()),
-            inner: Box::pin(stream::once(async move { Ok(Bytes::from(buf)) })),
-        }
+        ByteStream::from(Bytes::from(buf))
     }
 }
 
@@ -90,23 +245,129 @@ impl Stream for ByteStream {
 }
 
 pin_project! {
-    struct ImplAsyncRead {
+    /// An in-memory [`ResettableByteStream`] backing `ByteStream`s built from `Bytes`/`Vec<u8>`.
+    struct ResettableBytesSource {
+        data: Bytes,
+        bytes_read: usize,
+    }
+}
+
+impl ResettableBytesSource {
+    fn new(data: Bytes) -> Self {
+        ResettableBytesSource {
+            data,
+            bytes_read: 0,
+        }
+    }
+}
+
+impl Stream for ResettableBytesSource {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.bytes_read >= this.data.len() {
+            return Poll::Ready(None);
+        }
+        let remaining = this.data.slice(*this.bytes_read..);
+        *this.bytes_read = this.data.len();
+        Poll::Ready(Some(Ok(remaining)))
+    }
+}
+
+#[async_trait]
+impl ResettableByteStream for ResettableBytesSource {
+    async fn reset(&mut self) -> io::Result<()> {
+        self.bytes_read = 0;
+        Ok(())
+    }
+}
+
+/// A file-backed (or otherwise seekable) [`ResettableByteStream`] backing `ByteStream`s built
+/// from [`ByteStream::from_seekable_async_read`].
+struct ResettableReaderSource<R> {
+    reader: R,
+    buf: BytesMut,
+    capacity: usize,
+    done: bool,
+}
+
+impl<R> ResettableReaderSource<R> {
+    fn new(reader: R, capacity: usize) -> Self {
+        ResettableReaderSource {
+            reader,
+            buf: BytesMut::new(),
+            capacity,
+            done: false,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> Stream for ResettableReaderSource<R> {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        if this.buf.capacity() == 0 {
+            this.buf.reserve(this.capacity);
+        }
+        let spare = this.buf.spare_capacity_mut();
+        let n = std::cmp::min(spare.len(), this.capacity);
+        let mut read_buf = ReadBuf::uninit(&mut spare[..n]);
+        match futures::ready!(Pin::new(&mut this.reader).poll_read(cx, &mut read_buf)) {
+            Err(e) => Poll::Ready(Some(Err(e))),
+            Ok(()) => {
+                let filled = read_buf.filled().len();
+                if filled == 0 {
+                    this.done = true;
+                    Poll::Ready(None)
+                } else {
+                    // Safety: `poll_read` only reports bytes as filled once it has
+                    // initialized them.
+                    unsafe {
+                        this.buf.advance_mut(filled);
+                    }
+                    Poll::Ready(Some(Ok(this.buf.split().freeze())))
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<R: AsyncRead + AsyncSeek + Unpin + Send> ResettableByteStream for ResettableReaderSource<R> {
+    async fn reset(&mut self) -> io::Result<()> {
+        self.reader.seek(SeekFrom::Start(0)).await?;
+        self.buf.clear();
+        self.done = false;
+        Ok(())
+    }
+}
+
+pin_project! {
+    /// An `AsyncRead` (and `AsyncBufRead`) implementation returned by
+    /// [`ByteStream::into_async_read`].
+    pub struct ByteStreamReader {
         buffer: BytesMut,
         #[pin]
         stream: futures::stream::Fuse<Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>>>,
     }
 }
 
-impl ImplAsyncRead {
+impl ByteStreamReader {
     fn new(stream: Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>>) -> Self {
-        ImplAsyncRead {
+        ByteStreamReader {
             buffer: BytesMut::new(),
             stream: stream.fuse(),
         }
     }
 }
 
-impl AsyncRead for ImplAsyncRead {
+impl AsyncRead for ByteStreamReader {
     fn poll_read(
         self: Pin<&mut Self>,
         cx: &mut Context<'_>,
@@ -129,33 +390,130 @@ impl AsyncRead for ImplAsyncRead {
     }
 }
 
+impl AsyncBufRead for ByteStreamReader {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.project();
+        if this.buffer.is_empty() {
+            match futures::ready!(this.stream.poll_next(cx)) {
+                None => return Poll::Ready(Ok(&[])),
+                Some(Err(e)) => return Poll::Ready(Err(e)),
+                Some(Ok(bytes)) => {
+                    this.buffer.put(bytes);
+                }
+            }
+        }
+        Poll::Ready(Ok(&this.buffer[..]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.project();
+        this.buffer.advance(amt);
+    }
+}
+
+pin_project! {
+    /// Adapts an `AsyncRead` into a `Stream` of `Bytes` chunks of at most `capacity` bytes,
+    /// used to back [`ByteStream::from_async_read`].
+    struct ReaderStream<R> {
+        #[pin]
+        reader: Option<R>,
+        buf: BytesMut,
+        capacity: usize,
+    }
+}
+
+impl<R> ReaderStream<R> {
+    fn new(reader: R, capacity: usize) -> Self {
+        ReaderStream {
+            reader: Some(reader),
+            buf: BytesMut::new(),
+            capacity,
+        }
+    }
+}
+
+impl<R: AsyncRead> Stream for ReaderStream<R> {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let reader = match this.reader.as_mut().as_pin_mut() {
+            Some(reader) => reader,
+            None => return Poll::Ready(None),
+        };
+
+        if this.buf.capacity() == 0 {
+            this.buf.reserve(*this.capacity);
+        }
+        let spare = this.buf.spare_capacity_mut();
+        let n = std::cmp::min(spare.len(), *this.capacity);
+        let mut read_buf = ReadBuf::uninit(&mut spare[..n]);
+        match futures::ready!(reader.poll_read(cx, &mut read_buf)) {
+            Err(e) => {
+                this.reader.set(None);
+                Poll::Ready(Some(Err(e)))
+            }
+            Ok(()) => {
+                let filled = read_buf.filled().len();
+                if filled == 0 {
+                    this.reader.set(None);
+                    Poll::Ready(None)
+                } else {
+                    // Safety: `poll_read` only reports bytes as filled once it has
+                    // initialized them.
+                    unsafe {
+                        this.buf.advance_mut(filled);
+                    }
+                    Poll::Ready(Some(Ok(this.buf.split().freeze())))
+                }
+            }
+        }
+    }
+}
+
 pin_project! {
     struct ImplBlockingRead {
         #[pin]
-        inner: ImplAsyncRead,
+        inner: ByteStreamReader,
+        rt: Option<tokio::runtime::Runtime>,
     }
 }
 
 impl ImplBlockingRead {
     fn new(stream: Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>>) -> Self {
         ImplBlockingRead {
-            inner: ImplAsyncRead::new(stream),
+            inner: ByteStreamReader::new(stream),
+            rt: None,
         }
     }
 }
 
 impl io::Read for ImplBlockingRead {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let rt = tokio::runtime::Runtime::new()?;
-        rt.block_on(future::poll_fn(|cx| {
+        let inner = &mut self.inner;
+        let fut = future::poll_fn(move |cx| {
             let mut buf = ReadBuf::new(buf);
-            futures::ready!(AsyncRead::poll_read(
-                Pin::new(&mut self.inner),
-                cx,
-                &mut buf
-            ))?;
+            futures::ready!(AsyncRead::poll_read(Pin::new(inner), cx, &mut buf))?;
             Poll::Ready(Ok(buf.filled().len()))
-        }))
+        });
+        // If we're already running inside a Tokio context (e.g. this is being driven from a
+        // `tokio::task::spawn_blocking`), blocking on our own runtime would panic; fall back to
+        // blocking on the current one instead.
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle.block_on(fut),
+            Err(_) => {
+                if self.rt.is_none() {
+                    let rt = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()?;
+                    self.rt = Some(rt);
+                }
+                self.rt
+                    .as_ref()
+                    .expect("runtime was just initialized above")
+                    .block_on(fut)
+            }
+        }
     }
 }
 
@@ -233,3 +591,144 @@ async fn test_new_with_size_read() {
     assert_eq!(&buf[..1], b"8");
     assert_eq!(async_read.read(&mut buf).await.unwrap(), 0);
 }
+
+#[tokio::test]
+async fn test_async_buf_read() {
+    use bytes::Bytes;
+    use tokio::io::AsyncBufReadExt;
+
+    let chunks = vec![
+        Ok(Bytes::from_static(b"foo\nb")),
+        Ok(Bytes::from_static(b"ar\nbaz")),
+    ];
+    let stream = ByteStream::new(stream::iter(chunks));
+    let mut async_read = stream.into_async_read();
+
+    let mut line = String::new();
+    assert_eq!(async_read.read_line(&mut line).await.unwrap(), 4);
+    assert_eq!(line, "foo\n");
+
+    line.clear();
+    assert_eq!(async_read.read_line(&mut line).await.unwrap(), 4);
+    assert_eq!(line, "bar\n");
+
+    line.clear();
+    assert_eq!(async_read.read_line(&mut line).await.unwrap(), 3);
+    assert_eq!(line, "baz");
+
+    line.clear();
+    assert_eq!(async_read.read_line(&mut line).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_from_async_read() {
+    use std::io::Cursor;
+
+    let data = b"the quick brown fox jumps over the lazy dog".to_vec();
+    let reader = Cursor::new(data.clone());
+    let stream = ByteStream::from_async_read_with_capacity(reader, Some(data.len()), 4);
+
+    assert_eq!(stream.size_hint(), Some(data.len()));
+
+    let chunks: Vec<Bytes> = stream.map(|chunk| chunk.unwrap()).collect().await;
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 4));
+    let collected: Vec<u8> = chunks.into_iter().flatten().collect();
+    assert_eq!(collected, data);
+}
+
+#[tokio::test]
+#[should_panic(expected = "capacity must be greater than zero")]
+async fn test_from_async_read_rejects_zero_capacity() {
+    use std::io::Cursor;
+
+    let reader = Cursor::new(b"hello world".to_vec());
+    ByteStream::from_async_read_with_capacity(reader, None, 0);
+}
+
+#[tokio::test]
+async fn test_bytes_stream_is_resettable() {
+    let data = b"hello resettable world".to_vec();
+    let mut stream = ByteStream::from(data.clone());
+    assert!(stream.is_resettable());
+
+    let first: Vec<u8> = (&mut stream)
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<Bytes>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(first, data);
+
+    assert!(stream.reset().await.unwrap());
+
+    let second: Vec<u8> = stream
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<Bytes>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(second, data);
+}
+
+#[tokio::test]
+async fn test_arbitrary_stream_is_not_resettable() {
+    let chunks = vec![Ok(Bytes::from_static(b"1234"))];
+    let mut stream = ByteStream::new(stream::iter(chunks));
+    assert!(!stream.is_resettable());
+    assert!(!stream.reset().await.unwrap());
+}
+
+#[tokio::test]
+async fn test_seekable_async_read_is_resettable() {
+    use std::io::Cursor;
+
+    let data = b"the quick brown fox".to_vec();
+    let mut stream = ByteStream::from_seekable_async_read(Cursor::new(data.clone()), None);
+    assert!(stream.is_resettable());
+
+    let first: Vec<u8> = (&mut stream)
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<Bytes>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(first, data);
+
+    assert!(stream.reset().await.unwrap());
+
+    let second: Vec<u8> = stream
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<Bytes>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    assert_eq!(second, data);
+}
+
+#[tokio::test]
+#[should_panic(expected = "capacity must be greater than zero")]
+async fn test_from_seekable_async_read_rejects_zero_capacity() {
+    use std::io::Cursor;
+
+    let reader = Cursor::new(b"hello world".to_vec());
+    ByteStream::from_seekable_async_read_with_capacity(reader, None, 0);
+}
+
+#[tokio::test]
+async fn test_copy_to() {
+    let chunks = vec![
+        Ok(Bytes::from_static(b"1234")),
+        Ok(Bytes::from_static(b"5678")),
+    ];
+    let stream = ByteStream::new(stream::iter(chunks));
+
+    let mut dest = Vec::new();
+    let written = stream.copy_to(&mut dest).await.unwrap();
+
+    assert_eq!(written, 8);
+    assert_eq!(dest, b"12345678");
+}